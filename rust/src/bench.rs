@@ -5,29 +5,164 @@
 /// 2. Orderbook engine throughput (updates/sec) — no channel overhead
 /// 3. End-to-end throughput with channel (engine → strategy)
 /// 4. Engine → strategy latency distribution
+/// 5. Encoding-format shootout (CSV, bincode, postcard, DTF over mmap)
+/// 6. Streaming (CsvReader::iter) vs batch (parse_all) load throughput
+/// 7. OHLC candle aggregation (1s, rolled up to 10s and 1m)
+/// 0. (optional, `--cold` flag) Cold vs warm load throughput, CSV vs DTF
+///
+/// Subcommand: `bench convert <csv_path> [out_path]` writes the CSV out in
+/// the DTF binary format without running any benchmarks.
+///
+/// Subcommand (`replay` feature): `bench replay <updates.ndjson> [out_path]`
+/// feeds a recorded newline-delimited JSON update stream through the engine
+/// and dumps the resulting notifications back out, for reproducible runs
+/// against a captured feed instead of the CSV fixture.
 
 mod types;
 mod orderbook;
 mod parser;
 mod strategy;
+mod histogram;
+mod dtf;
+mod candles;
+#[cfg(feature = "replay")]
+mod replay;
 
 use std::thread;
 use crossbeam_channel::bounded;
 use crate::orderbook::Orderbook;
 use crate::parser::CsvReader;
-use crate::strategy::run_strategy;
-use crate::types::BookNotification;
+use crate::strategy::{run_strategy, StrategyStats};
+use crate::types::{BookNotification, MarketConfig, Update};
 
 const WARMUP_ITERATIONS: usize = 5;
 const BENCH_ITERATIONS: usize = 20;
 
+/// One row of the Benchmark 5 encoding-format shootout.
+struct EncodingResult {
+    name: &'static str,
+    bytes_on_disk: u64,
+    updates_per_sec: f64,
+}
+
+/// `bench convert <csv_path> [out_path]` — write the CSV out in the dense
+/// binary tick format from chunk0-1, for cold/warm head-to-head comparisons.
+fn run_convert(args: &[String]) {
+    let csv_path = args.get(2).cloned().unwrap_or_else(|| "btc_orderbook_updates.csv".to_string());
+    let out_path = args.get(3).cloned().unwrap_or_else(|| format!("{}.obk", csv_path));
+
+    let reader = CsvReader::open(&csv_path).expect("Failed to open CSV");
+    let updates = reader.parse_all();
+    dtf::DtfWriter::write(&out_path, 0, &updates).expect("Failed to write DTF file");
+
+    // Read the header back to confirm the round-trip, rather than just trusting the writer.
+    let written = dtf::DtfReader::open(&out_path).expect("Failed to reopen DTF file");
+    println!(
+        "Converted {} updates: {} -> {} (symbol_id={})",
+        updates.len(),
+        csv_path,
+        out_path,
+        written.symbol_id()
+    );
+}
+
+/// `bench replay <updates.ndjson> [out_path]` — feed a recorded NDJSON
+/// update stream through the engine and dump the resulting notifications
+/// back out, so a prior run (or a captured exchange feed) can be replayed
+/// byte-for-byte instead of depending on the CSV fixture.
+#[cfg(feature = "replay")]
+fn run_replay(args: &[String]) {
+    let in_path = args.get(2).cloned().unwrap_or_else(|| "updates.ndjson".to_string());
+    let out_path = args.get(3).cloned().unwrap_or_else(|| format!("{}.notifications.ndjson", in_path));
+
+    let reader = replay::ReplayReader::open(&in_path).expect("Failed to open replay input");
+    let mut writer = replay::ReplayWriter::create(&out_path).expect("Failed to create replay output");
+    let mut book = Orderbook::new(MarketConfig::default());
+    let mut count: u64 = 0;
+
+    for update in reader {
+        let update = update.expect("Malformed replay line");
+        let notif = book.apply(&update, 0);
+        writer.write_notification(&notif).expect("Failed to write notification");
+        count += 1;
+    }
+    writer.flush().expect("Failed to flush replay output");
+    println!("Replayed {} updates: {} -> {}", count, in_path, out_path);
+}
+
+/// `bench --cold [csv_path]` — report warm (page-cache-resident) vs cold
+/// (freshly evicted) parse throughput for both CSV and the DTF binary
+/// format, so I/O + decode cost is visible instead of just decode cost.
+fn run_cold_warm_benchmark(csv_path: &str, clock: &quanta::Clock) {
+    println!("── Benchmark 0: Cold vs Warm Load ────────────────────");
+
+    let dtf_path = format!("{}.obk", csv_path);
+
+    // Drop caches once, up front, before either format does any warming
+    // reads. Warming CSV first and only then dropping caches would evict
+    // the pages we just warmed, silently turning "warm csv" into a cold
+    // read and breaking the warm/cold symmetry between the two formats.
+    parser::try_drop_system_caches();
+
+    // This untimed parse (re)populates the CSV's page cache, and writing
+    // the DTF file leaves its pages in the page cache too.
+    let reader = CsvReader::open(csv_path).expect("Failed to open CSV");
+    let updates = reader.parse_all();
+    dtf::DtfWriter::write(&dtf_path, 0, &updates).expect("Failed to write DTF file");
+    let dtf_reader = dtf::DtfReader::open(&dtf_path).expect("Failed to open DTF");
+
+    // Warm CSV: pages are already cached by the parse above.
+    let start = clock.raw();
+    let warm_csv = reader.parse_all();
+    let warm_csv_ns = clock.delta_as_nanos(start, clock.raw()).max(1);
+
+    // Warm DTF: pages are already cached by the write above.
+    let start = clock.raw();
+    let warm_dtf = dtf_reader.decode_all();
+    let warm_dtf_ns = clock.delta_as_nanos(start, clock.raw()).max(1);
+
+    // Cold CSV: evict the mapped pages, then re-parse from disk.
+    reader.evict_from_cache().ok();
+    let start = clock.raw();
+    let cold_csv = reader.parse_all();
+    let cold_csv_ns = clock.delta_as_nanos(start, clock.raw()).max(1);
+
+    // Cold DTF.
+    dtf_reader.evict_from_cache().ok();
+    let start = clock.raw();
+    let cold_dtf = dtf_reader.decode_all();
+    let cold_dtf_ns = clock.delta_as_nanos(start, clock.raw()).max(1);
+
+    let tp = |n: usize, ns: u64| (n as f64 / ns as f64) * 1_000_000_000.0;
+
+    println!("  {:<10} {:>18} {:>18}", "format", "warm updates/sec", "cold updates/sec");
+    println!("  {:<10} {:>18.0} {:>18.0}", "csv", tp(warm_csv.len(), warm_csv_ns), tp(cold_csv.len(), cold_csv_ns));
+    println!("  {:<10} {:>18.0} {:>18.0}\n", "dtf", tp(warm_dtf.len(), warm_dtf_ns), tp(cold_dtf.len(), cold_dtf_ns));
+
+    std::fs::remove_file(&dtf_path).ok();
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
-    let csv_path = if args.len() > 1 {
-        args[1].clone()
-    } else {
-        "btc_orderbook_updates.csv".to_string()
-    };
+
+    if args.get(1).map(String::as_str) == Some("convert") {
+        run_convert(&args);
+        return;
+    }
+
+    #[cfg(feature = "replay")]
+    if args.get(1).map(String::as_str) == Some("replay") {
+        run_replay(&args);
+        return;
+    }
+
+    let cold_mode = args.iter().any(|a| a == "--cold");
+    let csv_path = args
+        .iter()
+        .skip(1)
+        .find(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "btc_orderbook_updates.csv".to_string());
 
     println!("╔══════════════════════════════════════════════════════╗");
     println!("║       ORDERBOOK SYSTEM — BENCHMARK SUITE            ║");
@@ -35,6 +170,10 @@ fn main() {
 
     let clock = quanta::Clock::new();
 
+    if cold_mode {
+        run_cold_warm_benchmark(&csv_path, &clock);
+    }
+
     // ── Benchmark 1: CSV Parsing ──────────────────────────────────
     println!("── Benchmark 1: CSV Parsing ──────────────────────────");
     let reader = CsvReader::open(&csv_path).expect("Failed to open CSV");
@@ -72,7 +211,7 @@ fn main() {
 
     // Warmup
     for _ in 0..WARMUP_ITERATIONS {
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         for update in &updates_ref {
             book.apply(update, 0);
         }
@@ -80,7 +219,7 @@ fn main() {
 
     let mut engine_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
     for _ in 0..BENCH_ITERATIONS {
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         let start = clock.raw();
         for update in &updates_ref {
             book.apply(update, 0);
@@ -106,7 +245,11 @@ fn main() {
     println!("── Benchmark 3: End-to-End (engine + channel + strategy) ──");
 
     let mut e2e_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
-    let mut last_stats = None;
+    // Merge every iteration's `StrategyStats` into one histogram instead of
+    // keeping only the last run, so Benchmark 4's percentiles reflect the
+    // whole benchmark — the same merge a multi-consumer production run would
+    // use to aggregate per-thread stats.
+    let mut last_stats = StrategyStats::new();
 
     for i in 0..BENCH_ITERATIONS {
         let (tx, rx) = bounded::<BookNotification>(4096);
@@ -117,7 +260,7 @@ fn main() {
             .spawn(move || run_strategy(rx, &strategy_clock, false))
             .unwrap();
 
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         let start = clock.raw();
 
         for update in &updates_ref {
@@ -130,7 +273,7 @@ fn main() {
         let stats = strategy_handle.join().unwrap();
         let end = clock.raw();
         e2e_times_ns.push(clock.delta_as_nanos(start, end));
-        last_stats = Some(stats);
+        last_stats.merge(&stats);
     }
 
     let avg_e2e_ns: u64 = e2e_times_ns.iter().sum::<u64>() / e2e_times_ns.len() as u64;
@@ -144,7 +287,8 @@ fn main() {
     // ── Benchmark 4: Latency Distribution ─────────────────────────
     println!("── Benchmark 4: Engine → Strategy Latency ────────────");
 
-    if let Some(stats) = &last_stats {
+    {
+        let stats = &last_stats;
         println!("  Samples:           {}", stats.count);
         println!("  Min latency:       {} ns", stats.min_latency_ns);
         println!("  Max latency:       {} ns", stats.max_latency_ns);
@@ -156,16 +300,166 @@ fn main() {
         println!("  P99.9 latency:     {} ns", stats.percentile(99.9));
     }
 
+    // ── Benchmark 5: Encoding Formats ─────────────────────────────
+    println!("\n── Benchmark 5: Encoding Formats ─────────────────────");
+
+    let csv_bytes_on_disk = std::fs::metadata(&csv_path).map(|m| m.len()).unwrap_or(0);
+    let mut format_results = Vec::new();
+
+    // CSV: re-use Benchmark 1's best parse time, file size is on-disk already.
+    format_results.push(EncodingResult {
+        name: "csv",
+        bytes_on_disk: csv_bytes_on_disk,
+        updates_per_sec: parse_throughput,
+    });
+
+    // bincode and postcard both derive against `Update`'s serde impls, which
+    // only exist under the `replay` feature (see types.rs) — without it
+    // they're plain structs/enums with no `Serialize`/`Deserialize` to call.
+    #[cfg(feature = "replay")]
+    {
+        // bincode
+        {
+            let encoded = bincode::serialize(&updates_ref).expect("bincode encode");
+            let mut decode_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
+            for _ in 0..BENCH_ITERATIONS {
+                let start = clock.raw();
+                let decoded: Vec<Update> = bincode::deserialize(&encoded).expect("bincode decode");
+                let end = clock.raw();
+                decode_times_ns.push(clock.delta_as_nanos(start, end));
+                std::hint::black_box(&decoded);
+            }
+            let min_ns = *decode_times_ns.iter().min().unwrap();
+            format_results.push(EncodingResult {
+                name: "bincode",
+                bytes_on_disk: encoded.len() as u64,
+                updates_per_sec: (updates_ref.len() as f64 / min_ns as f64) * 1_000_000_000.0,
+            });
+        }
+
+        // postcard
+        {
+            let encoded = postcard::to_allocvec(&updates_ref).expect("postcard encode");
+            let mut decode_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
+            for _ in 0..BENCH_ITERATIONS {
+                let start = clock.raw();
+                let decoded: Vec<Update> = postcard::from_bytes(&encoded).expect("postcard decode");
+                let end = clock.raw();
+                decode_times_ns.push(clock.delta_as_nanos(start, end));
+                std::hint::black_box(&decoded);
+            }
+            let min_ns = *decode_times_ns.iter().min().unwrap();
+            format_results.push(EncodingResult {
+                name: "postcard",
+                bytes_on_disk: encoded.len() as u64,
+                updates_per_sec: (updates_ref.len() as f64 / min_ns as f64) * 1_000_000_000.0,
+            });
+        }
+    }
+
+    // mmap'd DTF layout. Not zero-copy: `decode_all` copies
+    // every record out of the mapped bytes into a new `Vec<Update>`, with a
+    // fresh allocation for each snapshot's level lists.
+    {
+        let dtf_path = std::env::temp_dir().join("bench_shootout.obk");
+        dtf::DtfWriter::write(&dtf_path, 0, &updates_ref).expect("dtf encode");
+        let bytes_on_disk = std::fs::metadata(&dtf_path).map(|m| m.len()).unwrap_or(0);
+        let mut decode_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
+        for _ in 0..BENCH_ITERATIONS {
+            let reader = dtf::DtfReader::open(&dtf_path).expect("dtf open");
+            let start = clock.raw();
+            let decoded = reader.decode_all();
+            let end = clock.raw();
+            decode_times_ns.push(clock.delta_as_nanos(start, end));
+            std::hint::black_box(&decoded);
+        }
+        let min_ns = *decode_times_ns.iter().min().unwrap();
+        format_results.push(EncodingResult {
+            name: "dtf (mmap)",
+            bytes_on_disk,
+            updates_per_sec: (updates_ref.len() as f64 / min_ns as f64) * 1_000_000_000.0,
+        });
+        std::fs::remove_file(&dtf_path).ok();
+    }
+
+    #[cfg(not(feature = "replay"))]
+    println!("  (bincode, postcard skipped: build with --features replay to include them)");
+
+    println!("  {:<18} {:>16} {:>20}", "format", "bytes-on-disk", "updates/sec");
+    for r in &format_results {
+        println!("  {:<18} {:>16} {:>20.0}", r.name, r.bytes_on_disk, r.updates_per_sec);
+    }
+
+    // ── Benchmark 6: Streaming vs Batch Load ──────────────────────
+    println!("\n── Benchmark 6: Streaming End-to-End ─────────────────");
+
+    let mut stream_e2e_times_ns = Vec::with_capacity(BENCH_ITERATIONS);
+    for i in 0..BENCH_ITERATIONS {
+        let (tx, rx) = bounded::<BookNotification>(4096);
+        let strategy_clock = clock.clone();
+
+        let strategy_handle = thread::Builder::new()
+            .name(format!("bench-stream-strategy-{}", i))
+            .spawn(move || run_strategy(rx, &strategy_clock, false))
+            .unwrap();
+
+        let mut book = Orderbook::new(MarketConfig::default());
+        let start = clock.raw();
+
+        for update in reader.iter() {
+            let now_ns = clock.delta_as_nanos(0, clock.raw());
+            let notif = book.apply(&update, now_ns);
+            let _ = tx.send(notif);
+        }
+
+        drop(tx);
+        strategy_handle.join().unwrap();
+        let end = clock.raw();
+        stream_e2e_times_ns.push(clock.delta_as_nanos(start, end));
+    }
+
+    let avg_stream_ns: u64 = stream_e2e_times_ns.iter().sum::<u64>() / stream_e2e_times_ns.len() as u64;
+    let min_stream_ns: u64 = *stream_e2e_times_ns.iter().min().unwrap();
+    let stream_throughput = (updates_ref.len() as f64 / min_stream_ns as f64) * 1_000_000_000.0;
+
+    println!("  Avg stream e2e time: {:.2} µs", avg_stream_ns as f64 / 1000.0);
+    println!("  Min stream e2e time: {:.2} µs", min_stream_ns as f64 / 1000.0);
+    println!("  Streaming throughput: {:.0} updates/sec (best run)", stream_throughput);
+    println!("  Batch (parse_all) throughput:   {:.0} updates/sec (from Benchmark 3)\n", e2e_throughput);
+
+    // ── Benchmark 7: Candle Aggregation ───────────────────────────
+    println!("── Benchmark 7: Candle Aggregation ───────────────────");
+
+    const BUCKET_1S_NS: u64 = 1_000_000_000;
+
+    let mut book = Orderbook::new(MarketConfig::default());
+    let mut builder_1s = candles::CandleBuilder::new(BUCKET_1S_NS);
+    let start = clock.raw();
+    for update in &updates_ref {
+        let notif = book.apply(update, 0);
+        builder_1s.on_notification(&notif);
+    }
+    let candles_1s = builder_1s.finish();
+    let candle_ns = clock.delta_as_nanos(start, clock.raw());
+
+    let candles_10s = candles::roll_up(&candles_1s, 10);
+    let candles_1m = candles::roll_up(&candles_10s, 6);
+
+    println!("  Build time:        {:.2} µs", candle_ns as f64 / 1000.0);
+    println!("  1s candles:        {}", candles_1s.len());
+    println!("  10s candles:       {}", candles_10s.len());
+    println!("  1m candles:        {}", candles_1m.len());
+    println!();
+
     println!("\n╔══════════════════════════════════════════════════════╗");
     println!("║                   SUMMARY                           ║");
     println!("╠══════════════════════════════════════════════════════╣");
     println!("║  CSV parse throughput: {:>12.0} updates/sec     ║", parse_throughput);
     println!("║  Engine throughput:    {:>12.0} updates/sec     ║", engine_throughput);
     println!("║  E2E throughput:       {:>12.0} updates/sec     ║", e2e_throughput);
+    println!("║  Streaming throughput: {:>12.0} updates/sec     ║", stream_throughput);
     println!("║  Per-update latency:   {:>9.0} ns               ║", per_update_ns);
-    if let Some(stats) = &last_stats {
-        println!("║  Median chan latency:  {:>9} ns               ║", stats.median());
-        println!("║  P99 chan latency:     {:>9} ns               ║", stats.percentile(99.0));
-    }
+    println!("║  Median chan latency:  {:>9} ns               ║", last_stats.median());
+    println!("║  P99 chan latency:     {:>9} ns               ║", last_stats.percentile(99.0));
     println!("╚══════════════════════════════════════════════════════╝");
 }
@@ -0,0 +1,195 @@
+/// OHLC candle aggregation, driven by the book's mid-price.
+///
+/// Design:
+/// - `CandleBuilder` consumes a stream of `BookNotification`s and maintains
+///   one open candle at a time, keyed by `floor(update_timestamp / bucket_ns)`.
+/// - Empty buckets (no notification landed in them) are filled with a flat
+///   candle carrying the previous close forward, so the series has no holes
+///   — useful for downstream consumers that assume a fixed-cadence series.
+/// - `higher_order` rolls a slice of same-resolution candles up into one
+///   coarser candle, so e.g. 1s candles can be combined into 10s without
+///   re-reading the tick stream.
+
+use crate::types::{BookNotification, Price, Timestamp};
+
+/// One OHLC bar over `[bucket_start_ns, bucket_start_ns + bucket_ns)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub bucket_start_ns: Timestamp,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    /// Number of real ticks that landed in this bucket (0 for a gap-filled
+    /// flat candle).
+    pub tick_count: u64,
+}
+
+impl Candle {
+    fn opening(bucket_start_ns: Timestamp, price: Price) -> Self {
+        Self { bucket_start_ns, open: price, high: price, low: price, close: price, tick_count: 1 }
+    }
+
+    /// A synthetic candle for a bucket with no real ticks, carrying the
+    /// previous close forward as a flat OHLC.
+    fn flat(bucket_start_ns: Timestamp, price: Price) -> Self {
+        Self { bucket_start_ns, open: price, high: price, low: price, close: price, tick_count: 0 }
+    }
+
+    fn update(&mut self, price: Price) {
+        if price.0 > self.high.0 {
+            self.high = price;
+        }
+        if price.0 < self.low.0 {
+            self.low = price;
+        }
+        self.close = price;
+        self.tick_count += 1;
+    }
+}
+
+/// Builds a gap-free series of fixed-width candles from a `BookNotification`
+/// stream.
+pub struct CandleBuilder {
+    bucket_ns: u64,
+    current: Option<Candle>,
+    finished: Vec<Candle>,
+}
+
+impl CandleBuilder {
+    pub fn new(bucket_ns: u64) -> Self {
+        Self { bucket_ns, current: None, finished: Vec::new() }
+    }
+
+    /// Feed one notification. Skips notifications where either side of the
+    /// book is empty, since there's no mid-price to sample.
+    pub fn on_notification(&mut self, notif: &BookNotification) {
+        let (bid, ask) = match (notif.best_bid, notif.best_ask) {
+            (Some(bid), Some(ask)) => (bid, ask),
+            _ => return,
+        };
+        let mid = Price((bid.price.0 + ask.price.0) / 2);
+        let bucket_key = notif.update_timestamp / self.bucket_ns;
+
+        match &mut self.current {
+            Some(candle) if candle.bucket_start_ns / self.bucket_ns == bucket_key => {
+                candle.update(mid);
+            }
+            Some(_) => {
+                let finished = self.current.take().unwrap();
+                let cur_key = finished.bucket_start_ns / self.bucket_ns;
+                let carry_close = finished.close;
+                self.finished.push(finished);
+
+                for gap_key in (cur_key + 1)..bucket_key {
+                    self.finished.push(Candle::flat(gap_key * self.bucket_ns, carry_close));
+                }
+                self.current = Some(Candle::opening(bucket_key * self.bucket_ns, mid));
+            }
+            None => {
+                self.current = Some(Candle::opening(bucket_key * self.bucket_ns, mid));
+            }
+        }
+    }
+
+    /// Flush the in-progress candle (if any) and return every finished
+    /// candle in bucket order. Consumes the builder's accumulated state.
+    pub fn finish(mut self) -> Vec<Candle> {
+        if let Some(candle) = self.current.take() {
+            self.finished.push(candle);
+        }
+        self.finished
+    }
+}
+
+/// Combine a slice of same-resolution candles into a single coarser one:
+/// open from the first, close from the last, high/low across all, tick
+/// counts summed. Returns `None` for an empty slice.
+pub fn higher_order(candles: &[Candle]) -> Option<Candle> {
+    let first = candles.first()?;
+    let last = candles.last()?;
+    let high = candles.iter().map(|c| c.high.0).max().unwrap();
+    let low = candles.iter().map(|c| c.low.0).min().unwrap();
+    let tick_count = candles.iter().map(|c| c.tick_count).sum();
+
+    Some(Candle {
+        bucket_start_ns: first.bucket_start_ns,
+        open: first.open,
+        high: Price(high),
+        low: Price(low),
+        close: last.close,
+        tick_count,
+    })
+}
+
+/// Roll a base-resolution candle series up into a coarser one by combining
+/// every `factor` consecutive candles via `higher_order`.
+pub fn roll_up(candles: &[Candle], factor: usize) -> Vec<Candle> {
+    candles.chunks(factor).filter_map(higher_order).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BookStatus, Level, Qty};
+
+    fn notif(ts: Timestamp, bid: f64, ask: f64) -> BookNotification {
+        BookNotification {
+            update_timestamp: ts,
+            engine_send_ns: 0,
+            best_bid: Some(Level { price: Price::from_f64(bid), qty: Qty(1.0) }),
+            best_ask: Some(Level { price: Price::from_f64(ask), qty: Qty(1.0) }),
+            seq: 0,
+            status: BookStatus::Live,
+            violation: None,
+        }
+    }
+
+    #[test]
+    fn test_single_bucket() {
+        let mut builder = CandleBuilder::new(1000);
+        builder.on_notification(&notif(0, 99.0, 101.0));
+        builder.on_notification(&notif(500, 100.0, 102.0));
+        let candles = builder.finish();
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].open, Price::from_f64(100.0));
+        assert_eq!(candles[0].high, Price::from_f64(101.0));
+        assert_eq!(candles[0].tick_count, 2);
+    }
+
+    #[test]
+    fn test_gap_fill_carries_close_forward() {
+        let mut builder = CandleBuilder::new(1000);
+        builder.on_notification(&notif(0, 99.0, 101.0)); // bucket 0, mid=100
+        builder.on_notification(&notif(3000, 104.0, 106.0)); // bucket 3, mid=105
+        let candles = builder.finish();
+
+        assert_eq!(candles.len(), 4);
+        assert_eq!(candles[0].tick_count, 1);
+        // Gap buckets 1 and 2 carry bucket 0's close forward, flat.
+        assert_eq!(candles[1].tick_count, 0);
+        assert_eq!(candles[1].open, candles[0].close);
+        assert_eq!(candles[1].close, candles[0].close);
+        assert_eq!(candles[2].tick_count, 0);
+        assert_eq!(candles[2].open, candles[0].close);
+        // Bucket 3 opens on its own first real tick, not the carried close.
+        assert_eq!(candles[3].tick_count, 1);
+        assert_eq!(candles[3].open, Price::from_f64(105.0));
+    }
+
+    #[test]
+    fn test_higher_order_rollup() {
+        let mut builder = CandleBuilder::new(1000);
+        for i in 0..10u64 {
+            builder.on_notification(&notif(i * 1000, 99.0 + i as f64, 101.0 + i as f64));
+        }
+        let base = builder.finish();
+        assert_eq!(base.len(), 10);
+
+        let rolled = roll_up(&base, 10);
+        assert_eq!(rolled.len(), 1);
+        assert_eq!(rolled[0].open, base[0].open);
+        assert_eq!(rolled[0].close, base[9].close);
+        assert_eq!(rolled[0].tick_count, 10);
+    }
+}
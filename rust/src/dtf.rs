@@ -0,0 +1,300 @@
+/// Dense binary tick format (DTF) — an alternative to CSV loading.
+///
+/// Design choices:
+/// - Memory-mapped file I/O via `memmap2`, same as the CSV path.
+/// - Fixed-point `Price` is written directly (no f64 round-trip) and `Qty`
+///   is stored as a raw f64, matching `types::Price`/`types::Qty` exactly.
+/// - Timestamps are stored as a `u32` delta from a file-level `base_timestamp`
+///   to keep the common case small; a record tagged `*_LONG` carries the
+///   full `u64` timestamp for the rare delta that overflows `u32`.
+/// - Snapshots are variable-length, so they're written as a tag followed by
+///   a length-prefixed block of (price, qty) pairs for each side.
+///
+/// File layout:
+///   magic: [u8; 4]       — b"OBK1"
+///   symbol_id: u64
+///   record_count: u64    — number of top-level records (snapshots count as one)
+///   base_timestamp: u64
+///   records: [Record]    — see `encode_record` / `decode_record`
+
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use crate::types::*;
+
+const MAGIC: &[u8; 4] = b"OBK1";
+const HEADER_LEN: usize = 4 + 8 + 8 + 8;
+
+const TAG_INCREMENTAL_BID: u8 = 0;
+const TAG_INCREMENTAL_ASK: u8 = 1;
+const TAG_SNAPSHOT: u8 = 2;
+const TAG_INCREMENTAL_BID_LONG: u8 = 3;
+const TAG_INCREMENTAL_ASK_LONG: u8 = 4;
+const TAG_SNAPSHOT_LONG: u8 = 5;
+
+/// Writes `Update`s out in the dense binary tick format.
+pub struct DtfWriter;
+
+impl DtfWriter {
+    /// Convert an in-memory list of updates into a DTF file at `path`.
+    pub fn write<P: AsRef<Path>>(path: P, symbol_id: u64, updates: &[Update]) -> io::Result<()> {
+        let base_timestamp = updates.first().map(update_timestamp).unwrap_or(0);
+
+        let mut buf = Vec::with_capacity(HEADER_LEN + updates.len() * 17);
+        buf.extend_from_slice(MAGIC);
+        buf.extend_from_slice(&symbol_id.to_le_bytes());
+        buf.extend_from_slice(&(updates.len() as u64).to_le_bytes());
+        buf.extend_from_slice(&base_timestamp.to_le_bytes());
+
+        for update in updates {
+            encode_record(&mut buf, update, base_timestamp);
+        }
+
+        let mut file = File::create(path)?;
+        file.write_all(&buf)
+    }
+}
+
+/// Encode a single `Update` as a record, appending it to `buf`.
+fn encode_record(buf: &mut Vec<u8>, update: &Update, base_timestamp: Timestamp) {
+    match update {
+        Update::Incremental { timestamp, exchange_seq, side, level } => {
+            let delta = timestamp.checked_sub(base_timestamp);
+            match (side, delta.and_then(|d| u32::try_from(d).ok())) {
+                (Side::Bid, Some(d)) => {
+                    buf.push(TAG_INCREMENTAL_BID);
+                    buf.extend_from_slice(&d.to_le_bytes());
+                }
+                (Side::Ask, Some(d)) => {
+                    buf.push(TAG_INCREMENTAL_ASK);
+                    buf.extend_from_slice(&d.to_le_bytes());
+                }
+                (Side::Bid, None) => {
+                    buf.push(TAG_INCREMENTAL_BID_LONG);
+                    buf.extend_from_slice(&timestamp.to_le_bytes());
+                }
+                (Side::Ask, None) => {
+                    buf.push(TAG_INCREMENTAL_ASK_LONG);
+                    buf.extend_from_slice(&timestamp.to_le_bytes());
+                }
+            }
+            buf.extend_from_slice(&exchange_seq.to_le_bytes());
+            encode_level(buf, *level);
+        }
+        Update::Snapshot { timestamp, exchange_seq, bids, asks } => {
+            let delta = timestamp.checked_sub(base_timestamp).and_then(|d| u32::try_from(d).ok());
+            match delta {
+                Some(d) => {
+                    buf.push(TAG_SNAPSHOT);
+                    buf.extend_from_slice(&d.to_le_bytes());
+                }
+                None => {
+                    buf.push(TAG_SNAPSHOT_LONG);
+                    buf.extend_from_slice(&timestamp.to_le_bytes());
+                }
+            }
+            buf.extend_from_slice(&exchange_seq.to_le_bytes());
+            buf.extend_from_slice(&(bids.len() as u32).to_le_bytes());
+            for level in bids {
+                encode_level(buf, *level);
+            }
+            buf.extend_from_slice(&(asks.len() as u32).to_le_bytes());
+            for level in asks {
+                encode_level(buf, *level);
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn encode_level(buf: &mut Vec<u8>, level: Level) {
+    buf.extend_from_slice(&(level.price.0 as i64).to_le_bytes());
+    buf.extend_from_slice(&level.qty.0.to_le_bytes());
+}
+
+#[inline(always)]
+fn update_timestamp(update: &Update) -> Timestamp {
+    match update {
+        Update::Incremental { timestamp, .. } => *timestamp,
+        Update::Snapshot { timestamp, .. } => *timestamp,
+    }
+}
+
+/// Memory-mapped DTF reader. Holds the mmap and decodes updates on demand.
+pub struct DtfReader {
+    mmap: Mmap,
+    symbol_id: u64,
+    record_count: u64,
+    base_timestamp: Timestamp,
+}
+
+impl DtfReader {
+    /// Open and memory-map the DTF file, validating the header.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        if mmap.len() < HEADER_LEN || &mmap[0..4] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "bad DTF magic"));
+        }
+        let symbol_id = u64::from_le_bytes(mmap[4..12].try_into().unwrap());
+        let record_count = u64::from_le_bytes(mmap[12..20].try_into().unwrap());
+        let base_timestamp = u64::from_le_bytes(mmap[20..28].try_into().unwrap());
+
+        Ok(Self { mmap, symbol_id, record_count, base_timestamp })
+    }
+
+    /// The symbol id stored in the file header.
+    #[inline(always)]
+    pub fn symbol_id(&self) -> u64 {
+        self.symbol_id
+    }
+
+    /// Best-effort: advise the kernel to evict this file's mapped pages from
+    /// the page cache, mirroring `CsvReader::evict_from_cache`, so cold-start
+    /// comparisons between CSV and DTF loading are apples-to-apples.
+    ///
+    /// `DontNeed` only exists on `UncheckedAdvice`, reached through the
+    /// `unsafe` `unchecked_advise` — "unchecked" here refers to the
+    /// documented risk of reading evicted-but-still-mapped pages racing
+    /// with eviction, not memory unsafety. That race can't happen here: we
+    /// only ever re-read through this same `&self.mmap` after this call
+    /// returns, never concurrently with it.
+    #[cfg(unix)]
+    pub fn evict_from_cache(&self) -> io::Result<()> {
+        unsafe { self.mmap.unchecked_advise(memmap2::UncheckedAdvice::DontNeed) }
+    }
+
+    #[cfg(not(unix))]
+    pub fn evict_from_cache(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Decode every record into a pre-allocated `Vec<Update>`.
+    pub fn decode_all(&self) -> Vec<Update> {
+        let data = &self.mmap[HEADER_LEN..];
+        let mut updates = Vec::with_capacity(self.record_count as usize);
+        let mut pos = 0;
+
+        for _ in 0..self.record_count {
+            let (update, next) = decode_record(data, pos, self.base_timestamp);
+            updates.push(update);
+            pos = next;
+        }
+
+        updates
+    }
+}
+
+/// Decode a single record starting at `pos`, returning the update and the
+/// offset of the next record.
+fn decode_record(data: &[u8], pos: usize, base_timestamp: Timestamp) -> (Update, usize) {
+    let tag = data[pos];
+    let mut pos = pos + 1;
+
+    match tag {
+        TAG_INCREMENTAL_BID | TAG_INCREMENTAL_ASK => {
+            let delta = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
+            pos += 4;
+            let exchange_seq = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+            pos += 8;
+            let level = decode_level(data, pos);
+            pos += 16;
+            let side = if tag == TAG_INCREMENTAL_BID { Side::Bid } else { Side::Ask };
+            (Update::Incremental { timestamp: base_timestamp + delta as u64, exchange_seq, side, level }, pos)
+        }
+        TAG_INCREMENTAL_BID_LONG | TAG_INCREMENTAL_ASK_LONG => {
+            let timestamp = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+            pos += 8;
+            let exchange_seq = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+            pos += 8;
+            let level = decode_level(data, pos);
+            pos += 16;
+            let side = if tag == TAG_INCREMENTAL_BID_LONG { Side::Bid } else { Side::Ask };
+            (Update::Incremental { timestamp, exchange_seq, side, level }, pos)
+        }
+        TAG_SNAPSHOT | TAG_SNAPSHOT_LONG => {
+            let timestamp = if tag == TAG_SNAPSHOT {
+                let delta = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
+                pos += 4;
+                base_timestamp + delta as u64
+            } else {
+                let ts = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+                pos += 8;
+                ts
+            };
+            let exchange_seq = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+            pos += 8;
+
+            let (bids, next) = decode_level_block(data, pos);
+            pos = next;
+            let (asks, next) = decode_level_block(data, pos);
+            pos = next;
+
+            (Update::Snapshot { timestamp, exchange_seq, bids, asks }, pos)
+        }
+        _ => panic!("unknown DTF record tag: {}", tag),
+    }
+}
+
+#[inline(always)]
+fn decode_level(data: &[u8], pos: usize) -> Level {
+    let price = i64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+    let qty = f64::from_le_bytes(data[pos + 8..pos + 16].try_into().unwrap());
+    Level { price: Price(price as u64), qty: Qty(qty) }
+}
+
+fn decode_level_block(data: &[u8], pos: usize) -> (Vec<Level>, usize) {
+    let len = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    let mut pos = pos + 4;
+    let mut levels = Vec::with_capacity(len);
+    for _ in 0..len {
+        levels.push(decode_level(data, pos));
+        pos += 16;
+    }
+    (levels, pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_updates() -> Vec<Update> {
+        vec![
+            Update::Snapshot {
+                timestamp: 1_700_000_000_000,
+                exchange_seq: 1,
+                bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+                asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(2.0) }],
+            },
+            Update::Incremental {
+                timestamp: 1_700_000_000_100,
+                exchange_seq: 2,
+                side: Side::Bid,
+                level: Level { price: Price::from_f64(99.5), qty: Qty(0.5) },
+            },
+            Update::Incremental {
+                timestamp: 1_700_000_005_000,
+                exchange_seq: 3,
+                side: Side::Ask,
+                level: Level { price: Price::from_f64(102.0), qty: Qty(0.0) },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("dtf_roundtrip_test.obk");
+        let updates = sample_updates();
+
+        DtfWriter::write(&path, 42, &updates).unwrap();
+        let reader = DtfReader::open(&path).unwrap();
+        let decoded = reader.decode_all();
+
+        assert_eq!(reader.symbol_id(), 42);
+        assert_eq!(decoded.len(), updates.len());
+        std::fs::remove_file(&path).ok();
+    }
+}
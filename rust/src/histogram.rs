@@ -0,0 +1,137 @@
+/// Fixed-size latency histogram for `StrategyStats`.
+///
+/// Replaces the previous clone-and-sort-the-whole-`Vec` approach: `record`
+/// is an O(1) bucket increment instead of an unbounded push, and
+/// `percentile` scans a fixed number of buckets instead of sorting on every
+/// call — both matter here since cloning/sorting samples to measure
+/// latency distorts the very latencies being measured.
+///
+/// Buckets are linear (one per nanosecond) below `LINEAR_NS`, where this
+/// crate's latencies actually live, then geometric above it — each octave
+/// of nanoseconds split into `SUBBUCKETS` equal-width buckets, so relative
+/// resolution (and percentile error) stays bounded up to 2^`MAX_OCTAVE` ns
+/// without needing an unbounded number of buckets. Latencies at or above
+/// 2^`MAX_OCTAVE` ns all land in the last bucket.
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+}
+
+impl LatencyHistogram {
+    const LINEAR_NS: u64 = 4096;
+    const MIN_OCTAVE: u32 = Self::LINEAR_NS.ilog2();
+    const MAX_OCTAVE: u32 = 40; // 2^40 ns ≈ 18.3 minutes — far past anything measured here.
+    const SUBBUCKETS: u32 = 64;
+    const NUM_BUCKETS: usize =
+        Self::LINEAR_NS as usize + ((Self::MAX_OCTAVE - Self::MIN_OCTAVE) * Self::SUBBUCKETS) as usize;
+
+    pub fn new() -> Self {
+        Self { buckets: vec![0u64; Self::NUM_BUCKETS] }
+    }
+
+    #[inline(always)]
+    fn bucket_index(latency_ns: u64) -> usize {
+        if latency_ns < Self::LINEAR_NS {
+            return latency_ns as usize;
+        }
+        let octave = (63 - latency_ns.leading_zeros()).min(Self::MAX_OCTAVE - 1);
+        let octave_start = 1u64 << octave;
+        let sub = ((latency_ns - octave_start) * Self::SUBBUCKETS as u64 / octave_start) as u32;
+        let sub = sub.min(Self::SUBBUCKETS - 1);
+        Self::LINEAR_NS as usize + ((octave - Self::MIN_OCTAVE) * Self::SUBBUCKETS + sub) as usize
+    }
+
+    /// The `[start, end)` nanosecond range a bucket covers, for interpolating
+    /// within it.
+    fn bucket_range(bucket: usize) -> (u64, u64) {
+        if bucket < Self::LINEAR_NS as usize {
+            let ns = bucket as u64;
+            return (ns, ns + 1);
+        }
+        let offset = (bucket - Self::LINEAR_NS as usize) as u32;
+        let octave = Self::MIN_OCTAVE + offset / Self::SUBBUCKETS;
+        let sub = (offset % Self::SUBBUCKETS) as u64;
+        let octave_start = 1u64 << octave;
+        let width = octave_start / Self::SUBBUCKETS as u64;
+        (octave_start + sub * width, octave_start + (sub + 1) * width)
+    }
+
+    #[inline(always)]
+    pub fn record(&mut self, latency_ns: u64) {
+        let idx = Self::bucket_index(latency_ns).min(self.buckets.len() - 1);
+        self.buckets[idx] += 1;
+    }
+
+    /// Interpolated percentile over `count` recorded samples (the caller's
+    /// exact running count, since the histogram itself only stores bucket
+    /// totals).
+    pub fn percentile(&self, p: f64, count: u64) -> u64 {
+        if count == 0 {
+            return 0;
+        }
+        let rank = ((p / 100.0) * (count as f64 - 1.0)).round() as u64;
+        let mut cumulative = 0u64;
+        for (idx, &bucket_count) in self.buckets.iter().enumerate() {
+            if bucket_count == 0 {
+                continue;
+            }
+            let next_cumulative = cumulative + bucket_count;
+            if rank < next_cumulative {
+                let (start, end) = Self::bucket_range(idx);
+                let frac = (rank - cumulative) as f64 / bucket_count as f64;
+                return start + (frac * (end - start) as f64) as u64;
+            }
+            cumulative = next_cumulative;
+        }
+        Self::bucket_range(self.buckets.len() - 1).1
+    }
+
+    /// Fold another histogram's counts into this one, bucket-for-bucket —
+    /// for aggregating per-thread/per-consumer `StrategyStats` in a
+    /// multi-consumer benchmark run.
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (mine, theirs) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *mine += theirs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_median_in_linear_range() {
+        let mut h = LatencyHistogram::new();
+        for ns in [10, 20, 30, 40, 50] {
+            h.record(ns);
+        }
+        assert_eq!(h.percentile(50.0, 5), 30);
+        assert_eq!(h.percentile(0.0, 5), 10);
+        assert_eq!(h.percentile(100.0, 5), 50);
+    }
+
+    #[test]
+    fn test_geometric_bucket_is_approximate() {
+        let mut h = LatencyHistogram::new();
+        h.record(1_000_000); // 1ms, well above the linear range
+        let p = h.percentile(50.0, 1);
+        // Relative error is bounded by 1/SUBBUCKETS within the containing octave.
+        assert!(p.abs_diff(1_000_000) < 1_000_000 / (LatencyHistogram::SUBBUCKETS as u64));
+    }
+
+    #[test]
+    fn test_merge_combines_counts() {
+        let mut a = LatencyHistogram::new();
+        let mut b = LatencyHistogram::new();
+        for ns in [10, 20, 30] {
+            a.record(ns);
+        }
+        for ns in [40, 50] {
+            b.record(ns);
+        }
+        a.merge(&b);
+        assert_eq!(a.percentile(0.0, 5), 10);
+        assert_eq!(a.percentile(100.0, 5), 50);
+        assert_eq!(a.percentile(50.0, 5), 30);
+    }
+}
@@ -1,9 +1,9 @@
 /// Ultra-low-latency orderbook system — main entry point.
 ///
 /// Architecture:
-///   [CSV mmap reader] → parse_all() → Vec<Update>
+///   [CSV mmap reader] → reader.iter() → Update (streamed one line at a time)
 ///        ↓
-///   [Engine thread] — iterates updates, applies to Orderbook, sends notification
+///   [Engine thread] — applies each update to Orderbook as it's decoded, sends notification
 ///        ↓ (crossbeam bounded channel, capacity 4096)
 ///   [Strategy thread] — receives notifications, logs best bid/ask, measures latency
 ///
@@ -14,13 +14,14 @@ mod types;
 mod orderbook;
 mod parser;
 mod strategy;
+mod histogram;
 
 use std::thread;
 use crossbeam_channel::bounded;
 use crate::orderbook::Orderbook;
 use crate::parser::CsvReader;
 use crate::strategy::run_strategy;
-use crate::types::BookNotification;
+use crate::types::{BookNotification, MarketConfig};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -33,15 +34,9 @@ fn main() {
     println!("=== Orderbook System ===");
     println!("Loading CSV: {}", csv_path);
 
-    // Phase 1: Parse CSV (memory-mapped, fast)
+    // Phase 1: Open CSV (memory-mapped); updates are decoded lazily below so
+    // parsing overlaps with engine work instead of blocking on a full Vec.
     let reader = CsvReader::open(&csv_path).expect("Failed to open CSV file");
-    let updates = reader.parse_all();
-    println!("Parsed {} updates from CSV", updates.len());
-
-    if updates.is_empty() {
-        eprintln!("No updates found in CSV. Exiting.");
-        return;
-    }
 
     // Phase 2: Set up clock and channel
     let clock = quanta::Clock::new();
@@ -60,13 +55,19 @@ fn main() {
         })
         .expect("Failed to spawn strategy thread");
 
-    // Phase 4: Engine — apply updates and send notifications
-    let mut book = Orderbook::new();
+    // Phase 4: Engine — decode, apply and send notifications, one update at
+    // a time, straight off the mmap.
+    let config = MarketConfig::default();
+    let mut book = Orderbook::new(config);
+    let mut update_count: u64 = 0;
+    let mut last_timestamp: u64 = 0;
     let start = clock.raw();
 
-    for update in &updates {
+    for update in reader.iter() {
         let now_ns = clock.delta_as_nanos(0, clock.raw());
-        let notif = book.apply(update, now_ns);
+        let notif = book.apply(&update, now_ns);
+        last_timestamp = notif.update_timestamp;
+        update_count += 1;
 
         // Send to strategy. If strategy is too slow, this will block (backpressure).
         if tx.send(notif).is_err() {
@@ -78,6 +79,10 @@ fn main() {
     let end = clock.raw();
     let elapsed_ns = clock.delta_as_nanos(start, end);
 
+    if update_count == 0 {
+        eprintln!("No updates found in CSV.");
+    }
+
     // Drop sender to signal strategy to stop
     drop(tx);
 
@@ -88,23 +93,41 @@ fn main() {
     let elapsed_us = elapsed_ns as f64 / 1_000.0;
     let elapsed_ms = elapsed_ns as f64 / 1_000_000.0;
     let throughput = if elapsed_ns > 0 {
-        (updates.len() as f64 / elapsed_ns as f64) * 1_000_000_000.0
+        (update_count as f64 / elapsed_ns as f64) * 1_000_000_000.0
     } else {
         0.0
     };
 
     println!("\n=== Engine Summary ===");
-    println!("Total updates:     {}", updates.len());
+    println!("Total updates:     {}", update_count);
     println!("Engine time:       {:.2} ms ({:.2} µs)", elapsed_ms, elapsed_us);
     println!("Throughput:        {:.0} updates/sec", throughput);
     println!("Final book depth:  {} bids, {} asks", book.bid_depth(), book.ask_depth());
     if let Some(bb) = book.best_bid() {
-        println!("Final best bid:    {:.2} @ {:.4}", bb.price.to_f64(), bb.qty.0);
+        println!("Final best bid:    {:.2} @ {:.4}", bb.price.to_f64_scaled(config.price_scale), bb.qty.0);
     }
     if let Some(ba) = book.best_ask() {
-        println!("Final best ask:    {:.2} @ {:.4}", ba.price.to_f64(), ba.qty.0);
+        println!("Final best ask:    {:.2} @ {:.4}", ba.price.to_f64_scaled(config.price_scale), ba.qty.0);
     }
 
+    // An opt-in depth subscriber, off the hot path — see `Orderbook::depth_notification`.
+    let depth = book.depth_notification(last_timestamp, elapsed_ns, 3);
+    let fmt_levels = |levels: &[types::Level]| -> String {
+        levels
+            .iter()
+            .map(|l| format!("{:.2}@{:.4}", l.price.to_f64_scaled(config.price_scale), l.qty.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    println!(
+        "Depth snapshot:    seq={} ts={} engine_send={}ns top 3 bids=[{}] top 3 asks=[{}]",
+        depth.seq,
+        depth.update_timestamp,
+        depth.engine_send_ns,
+        fmt_levels(&depth.bids),
+        fmt_levels(&depth.asks)
+    );
+
     println!("\n=== Strategy Latency (engine→strategy) ===");
     println!("Updates received:  {}", stats.count);
     println!("Min latency:       {} ns", stats.min_latency_ns);
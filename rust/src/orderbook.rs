@@ -25,28 +25,50 @@ pub struct Orderbook {
     cached_best_ask: Option<Level>,
     /// Monotonic sequence counter.
     seq: u64,
+    /// Last applied exchange-provided sequence number, if any.
+    last_exchange_seq: Option<u64>,
+    /// Whether the book is consistent with the exchange feed. See
+    /// `types::BookStatus`.
+    status: BookStatus,
+    /// Per-instrument tick/lot/min-size parameters. See `types::MarketConfig`.
+    config: MarketConfig,
 }
 
 #[allow(dead_code)]
 impl Orderbook {
-    /// Create an empty orderbook.
+    /// Create an empty orderbook for the given instrument's market config.
     #[inline]
-    pub fn new() -> Self {
+    pub fn new(config: MarketConfig) -> Self {
         Self {
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
             cached_best_bid: None,
             cached_best_ask: None,
             seq: 0,
+            last_exchange_seq: None,
+            status: BookStatus::Live,
+            config,
         }
     }
 
     /// Apply an update and return a notification for strategy consumers.
+    ///
+    /// Tracks the exchange-provided sequence number: if an incremental's
+    /// `exchange_seq` doesn't immediately follow the last applied one, the
+    /// book is marked `Stale` and further incrementals are dropped (not
+    /// applied) until the next `Snapshot` re-seats it.
+    ///
+    /// Levels are also checked against `MarketConfig` (tick size, lot size,
+    /// minimum size); a level that violates it is dropped rather than
+    /// applied, and the violation is surfaced on the returned notification
+    /// instead of silently distorting the book.
     #[inline]
     pub fn apply(&mut self, update: &Update, send_ns: u64) -> BookNotification {
         match update {
-            Update::Snapshot { timestamp, bids, asks } => {
-                self.apply_snapshot(bids, asks);
+            Update::Snapshot { timestamp, exchange_seq, bids, asks } => {
+                let violation = self.apply_snapshot(bids, asks);
+                self.last_exchange_seq = Some(*exchange_seq);
+                self.status = BookStatus::Live;
                 self.seq += 1;
                 BookNotification {
                     update_timestamp: *timestamp,
@@ -54,10 +76,27 @@ impl Orderbook {
                     best_bid: self.cached_best_bid,
                     best_ask: self.cached_best_ask,
                     seq: self.seq,
+                    status: self.status,
+                    violation,
                 }
             }
-            Update::Incremental { timestamp, side, level } => {
-                self.apply_incremental(*side, *level);
+            Update::Incremental { timestamp, exchange_seq, side, level } => {
+                let mut violation = None;
+                if self.status == BookStatus::Live {
+                    let contiguous = match self.last_exchange_seq {
+                        Some(last) => *exchange_seq == last + 1,
+                        None => true,
+                    };
+                    if contiguous {
+                        match self.validate_level(*level) {
+                            Ok(()) => self.apply_incremental(*side, *level),
+                            Err(v) => violation = Some(v),
+                        }
+                        self.last_exchange_seq = Some(*exchange_seq);
+                    } else {
+                        self.status = BookStatus::Stale;
+                    }
+                }
                 self.seq += 1;
                 BookNotification {
                     update_timestamp: *timestamp,
@@ -65,28 +104,64 @@ impl Orderbook {
                     best_bid: self.cached_best_bid,
                     best_ask: self.cached_best_ask,
                     seq: self.seq,
+                    status: self.status,
+                    violation,
                 }
             }
         }
     }
 
-    /// Apply a full snapshot: clear existing book and insert all levels.
+    /// Whether the book is currently believed consistent with the exchange
+    /// feed. See `types::BookStatus`.
+    #[inline(always)]
+    pub fn status(&self) -> BookStatus {
+        self.status
+    }
+
+    /// Validate a level against `MarketConfig`. A zero-qty level is always a
+    /// removal and bypasses validation — there's no new resting size to
+    /// check tick/lot/min-size against.
+    #[inline(always)]
+    fn validate_level(&self, level: Level) -> Result<(), MarketConfigViolation> {
+        if level.qty.is_zero() {
+            return Ok(());
+        }
+        self.config.validate(level)
+    }
+
+    /// Apply a full snapshot: clear existing book and insert all valid
+    /// levels. Returns the first `MarketConfig` violation encountered, if any.
     #[inline]
-    fn apply_snapshot(&mut self, bids: &[Level], asks: &[Level]) {
+    fn apply_snapshot(&mut self, bids: &[Level], asks: &[Level]) -> Option<MarketConfigViolation> {
         self.bids.clear();
         self.asks.clear();
+        let mut violation = None;
+
         for level in bids {
-            if !level.qty.is_zero() {
-                self.bids.insert(level.price, level.qty);
+            if level.qty.is_zero() {
+                continue;
+            }
+            match self.validate_level(*level) {
+                Ok(()) => {
+                    self.bids.insert(level.price, level.qty);
+                }
+                Err(v) => violation = violation.or(Some(v)),
             }
         }
         for level in asks {
-            if !level.qty.is_zero() {
-                self.asks.insert(level.price, level.qty);
+            if level.qty.is_zero() {
+                continue;
+            }
+            match self.validate_level(*level) {
+                Ok(()) => {
+                    self.asks.insert(level.price, level.qty);
+                }
+                Err(v) => violation = violation.or(Some(v)),
             }
         }
         self.refresh_best_bid();
         self.refresh_best_ask();
+        violation
     }
 
     /// Apply a single incremental update.
@@ -176,6 +251,36 @@ impl Orderbook {
         self.cached_best_ask
     }
 
+    /// Top `n` levels on `side`, best price first. Walks the BTreeMap in
+    /// reverse for bids (highest price first) and forward for asks (lowest
+    /// price first), stopping after `n` — for strategies that need more
+    /// than top-of-book (imbalance, weighted mid, sweep-cost estimation).
+    pub fn top_levels(&self, side: Side, n: usize) -> Vec<Level> {
+        match side {
+            Side::Bid => self.bids.iter().rev().take(n).map(|(&price, &qty)| Level { price, qty }).collect(),
+            Side::Ask => self.asks.iter().take(n).map(|(&price, &qty)| Level { price, qty }).collect(),
+        }
+    }
+
+    /// Top `n` levels on both sides. See `top_levels`.
+    pub fn depth_snapshot(&self, n: usize) -> (Vec<Level>, Vec<Level>) {
+        (self.top_levels(Side::Bid, n), self.top_levels(Side::Ask, n))
+    }
+
+    /// Build a `DepthNotification` for subscribers that opted in to more
+    /// than top-of-book. Kept separate from `apply` so the hot path (best
+    /// bid/ask only) pays nothing for subscribers that don't need depth.
+    pub fn depth_notification(&self, update_timestamp: Timestamp, engine_send_ns: u64, n: usize) -> DepthNotification {
+        let (bids, asks) = self.depth_snapshot(n);
+        DepthNotification {
+            update_timestamp,
+            engine_send_ns,
+            bids,
+            asks,
+            seq: self.seq,
+        }
+    }
+
     /// Number of bid levels.
     #[inline(always)]
     pub fn bid_depth(&self) -> usize {
@@ -195,9 +300,10 @@ mod tests {
 
     #[test]
     fn test_snapshot_and_best() {
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         let update = Update::Snapshot {
             timestamp: 1,
+            exchange_seq: 1,
             bids: vec![
                 Level { price: Price::from_f64(100.0), qty: Qty(1.0) },
                 Level { price: Price::from_f64(99.0), qty: Qty(2.0) },
@@ -214,9 +320,10 @@ mod tests {
 
     #[test]
     fn test_incremental_delete() {
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         let snap = Update::Snapshot {
             timestamp: 1,
+            exchange_seq: 2,
             bids: vec![
                 Level { price: Price::from_f64(100.0), qty: Qty(1.0) },
                 Level { price: Price::from_f64(99.0), qty: Qty(2.0) },
@@ -230,6 +337,7 @@ mod tests {
         // Delete best bid
         let del = Update::Incremental {
             timestamp: 2,
+            exchange_seq: 3,
             side: Side::Bid,
             level: Level { price: Price::from_f64(100.0), qty: Qty(0.0) },
         };
@@ -239,9 +347,10 @@ mod tests {
 
     #[test]
     fn test_incremental_new_best() {
-        let mut book = Orderbook::new();
+        let mut book = Orderbook::new(MarketConfig::default());
         let snap = Update::Snapshot {
             timestamp: 1,
+            exchange_seq: 4,
             bids: vec![
                 Level { price: Price::from_f64(100.0), qty: Qty(1.0) },
             ],
@@ -254,10 +363,167 @@ mod tests {
         // New best ask (lower)
         let upd = Update::Incremental {
             timestamp: 2,
+            exchange_seq: 5,
             side: Side::Ask,
             level: Level { price: Price::from_f64(101.0), qty: Qty(0.5) },
         };
         book.apply(&upd, 0);
         assert_eq!(book.best_ask().unwrap().price, Price::from_f64(101.0));
     }
+
+    #[test]
+    fn test_top_levels() {
+        let mut book = Orderbook::new(MarketConfig::default());
+        let snap = Update::Snapshot {
+            timestamp: 1,
+            exchange_seq: 6,
+            bids: vec![
+                Level { price: Price::from_f64(100.0), qty: Qty(1.0) },
+                Level { price: Price::from_f64(99.0), qty: Qty(2.0) },
+                Level { price: Price::from_f64(98.0), qty: Qty(3.0) },
+            ],
+            asks: vec![
+                Level { price: Price::from_f64(101.0), qty: Qty(1.5) },
+                Level { price: Price::from_f64(102.0), qty: Qty(3.0) },
+            ],
+        };
+        book.apply(&snap, 0);
+
+        let bids = book.top_levels(Side::Bid, 2);
+        assert_eq!(bids.len(), 2);
+        assert_eq!(bids[0].price, Price::from_f64(100.0));
+        assert_eq!(bids[1].price, Price::from_f64(99.0));
+
+        let asks = book.top_levels(Side::Ask, 5);
+        assert_eq!(asks.len(), 2);
+        assert_eq!(asks[0].price, Price::from_f64(101.0));
+
+        let (bids, asks) = book.depth_snapshot(1);
+        assert_eq!(bids[0].price, Price::from_f64(100.0));
+        assert_eq!(asks[0].price, Price::from_f64(101.0));
+    }
+
+    #[test]
+    fn test_sequence_gap_marks_stale_and_drops_incrementals() {
+        let mut book = Orderbook::new(MarketConfig::default());
+        let snap = Update::Snapshot {
+            timestamp: 1,
+            exchange_seq: 10,
+            bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+            asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(1.0) }],
+        };
+        let notif = book.apply(&snap, 0);
+        assert_eq!(notif.status, BookStatus::Live);
+
+        // Exchange seq jumps from 10 to 13 — a gap.
+        let gapped = Update::Incremental {
+            timestamp: 2,
+            exchange_seq: 13,
+            side: Side::Bid,
+            level: Level { price: Price::from_f64(105.0), qty: Qty(1.0) },
+        };
+        let notif = book.apply(&gapped, 0);
+        assert_eq!(notif.status, BookStatus::Stale);
+        assert_eq!(book.status(), BookStatus::Stale);
+        // The gapped incremental must not have been applied.
+        assert_eq!(book.best_bid().unwrap().price, Price::from_f64(100.0));
+
+        // Further incrementals are dropped while stale, even a contiguous one.
+        let dropped = Update::Incremental {
+            timestamp: 3,
+            exchange_seq: 14,
+            side: Side::Bid,
+            level: Level { price: Price::from_f64(110.0), qty: Qty(1.0) },
+        };
+        book.apply(&dropped, 0);
+        assert_eq!(book.best_bid().unwrap().price, Price::from_f64(100.0));
+
+        // A new snapshot re-seats the book and clears the stale flag.
+        let resync = Update::Snapshot {
+            timestamp: 4,
+            exchange_seq: 20,
+            bids: vec![Level { price: Price::from_f64(200.0), qty: Qty(1.0) }],
+            asks: vec![Level { price: Price::from_f64(201.0), qty: Qty(1.0) }],
+        };
+        let notif = book.apply(&resync, 0);
+        assert_eq!(notif.status, BookStatus::Live);
+        assert_eq!(book.best_bid().unwrap().price, Price::from_f64(200.0));
+    }
+
+    #[test]
+    fn test_invalid_tick_size_is_dropped_not_applied() {
+        // 50-raw-unit ticks (half a dollar at the default ×100 price scale).
+        let config = MarketConfig { price_scale: 100.0, tick_size: 50, lot_size: 0.0, min_size: 0.0 };
+        let mut book = Orderbook::new(config);
+        let snap = Update::Snapshot {
+            timestamp: 1,
+            exchange_seq: 1,
+            bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+            asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(1.0) }],
+        };
+        book.apply(&snap, 0);
+
+        // 100.01 -> raw price 10001, not a multiple of 50.
+        let bad = Update::Incremental {
+            timestamp: 2,
+            exchange_seq: 2,
+            side: Side::Bid,
+            level: Level { price: Price::from_f64(100.01), qty: Qty(1.0) },
+        };
+        let notif = book.apply(&bad, 0);
+        assert_eq!(notif.violation, Some(MarketConfigViolation::InvalidTickSize));
+        // The bad level must not have entered the book.
+        assert_eq!(book.best_bid().unwrap().price, Price::from_f64(100.0));
+        assert_eq!(book.bid_depth(), 1);
+    }
+
+    #[test]
+    fn test_invalid_lot_size_is_dropped_not_applied() {
+        let config = MarketConfig { price_scale: 100.0, tick_size: 1, lot_size: 0.5, min_size: 0.0 };
+        let mut book = Orderbook::new(config);
+        let snap = Update::Snapshot {
+            timestamp: 1,
+            exchange_seq: 1,
+            bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+            asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(1.0) }],
+        };
+        book.apply(&snap, 0);
+
+        // 0.3 isn't a multiple of the 0.5 lot size.
+        let bad = Update::Incremental {
+            timestamp: 2,
+            exchange_seq: 2,
+            side: Side::Ask,
+            level: Level { price: Price::from_f64(102.0), qty: Qty(0.3) },
+        };
+        let notif = book.apply(&bad, 0);
+        assert_eq!(notif.violation, Some(MarketConfigViolation::InvalidLotSize));
+        assert_eq!(book.ask_depth(), 1);
+        assert_eq!(book.best_ask().unwrap().price, Price::from_f64(101.0));
+    }
+
+    #[test]
+    fn test_below_minimum_size_is_dropped_not_applied() {
+        let config = MarketConfig { price_scale: 100.0, tick_size: 1, lot_size: 0.0, min_size: 1.0 };
+        let mut book = Orderbook::new(config);
+        let snap = Update::Snapshot {
+            timestamp: 1,
+            exchange_seq: 1,
+            bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+            asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(1.0) }],
+        };
+        book.apply(&snap, 0);
+
+        // 0.5 is below the 1.0 minimum — and nonzero, so it isn't treated as a removal.
+        let bad = Update::Incremental {
+            timestamp: 2,
+            exchange_seq: 2,
+            side: Side::Bid,
+            level: Level { price: Price::from_f64(99.0), qty: Qty(0.5) },
+        };
+        let notif = book.apply(&bad, 0);
+        assert_eq!(notif.violation, Some(MarketConfigViolation::BelowMinimumSize));
+        assert_eq!(book.bid_depth(), 1);
+        assert_eq!(book.best_bid().unwrap().price, Price::from_f64(100.0));
+    }
 }
@@ -5,6 +5,12 @@
 /// - Manual byte-level parsing — avoids allocation from csv crate overhead.
 /// - JSON arrays parsed with minimal serde_json — only for snapshot rows.
 /// - Incremental rows are parsed entirely without allocation.
+/// - Newline/comma scanning goes through `memchr`, which dispatches to SIMD
+///   routines on supported targets, so field boundaries are found in bulk
+///   rather than byte-by-byte.
+/// - Floating point fields are parsed straight off the `&[u8]` slice with
+///   `fast_float` (Eisel-Lemire), skipping the UTF-8 validation `str::parse`
+///   would otherwise force on every incremental row.
 
 use memmap2::Mmap;
 use std::fs::File;
@@ -14,11 +20,22 @@ use crate::types::*;
 /// Memory-mapped CSV reader. Holds the mmap and yields updates.
 pub struct CsvReader {
     mmap: Mmap,
+    /// Converts the CSV's decimal price fields to fixed-point `Price` units.
+    /// Defaults to `MarketConfig::default()` (×100 scale); use
+    /// `open_with_config` for instruments with a different tick granularity.
+    config: MarketConfig,
 }
 
 impl CsvReader {
-    /// Open and memory-map the CSV file.
+    /// Open and memory-map the CSV file, assuming `MarketConfig::default()`'s
+    /// ×100 price scale.
     pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::open_with_config(path, MarketConfig::default())
+    }
+
+    /// Open and memory-map the CSV file, converting decimal price fields to
+    /// fixed-point `Price` units via the given instrument's `MarketConfig`.
+    pub fn open_with_config<P: AsRef<Path>>(path: P, config: MarketConfig) -> std::io::Result<Self> {
         let file = File::open(path)?;
         let mmap = unsafe { Mmap::map(&file)? };
         // Advise the OS for sequential access
@@ -26,46 +43,107 @@ impl CsvReader {
         {
             let _ = mmap.advise(memmap2::Advice::Sequential);
         }
-        Ok(Self { mmap })
+        Ok(Self { mmap, config })
     }
 
     /// Parse all updates from the CSV into a pre-allocated Vec.
     /// We parse everything upfront to avoid allocation during the hot loop.
     pub fn parse_all(&self) -> Vec<Update> {
-        let data = &self.mmap[..];
         let mut updates = Vec::with_capacity(4096);
-        let mut pos = 0;
+        updates.extend(self.iter());
+        updates
+    }
+
+    /// Best-effort: advise the kernel to evict this file's mapped pages from
+    /// the page cache, so a subsequent `parse_all`/`iter` call genuinely
+    /// reads from disk rather than serving warm pages. Used by the bench
+    /// binary's cold-start mode.
+    ///
+    /// `DontNeed` only exists on `UncheckedAdvice`, reached through the
+    /// `unsafe` `unchecked_advise` — "unchecked" here refers to the
+    /// documented risk of reading evicted-but-still-mapped pages racing
+    /// with eviction, not memory unsafety. That race can't happen here: we
+    /// only ever re-read through this same `&self.mmap` after this call
+    /// returns, never concurrently with it.
+    #[cfg(unix)]
+    pub fn evict_from_cache(&self) -> std::io::Result<()> {
+        unsafe { self.mmap.unchecked_advise(memmap2::UncheckedAdvice::DontNeed) }
+    }
+
+    #[cfg(not(unix))]
+    pub fn evict_from_cache(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Iterate over updates one line at a time, decoding straight off the
+    /// mmap without materializing the whole file as a `Vec<Update>` first.
+    /// Lets a caller overlap parsing with engine/strategy work, and lets
+    /// datasets larger than RAM be processed without a giant upfront alloc.
+    pub fn iter(&self) -> CsvLineIter<'_> {
+        let data = &self.mmap[..];
+        let pos = skip_line(data, 0);
+        CsvLineIter { data, pos, next_seq: 1, config: self.config }
+    }
+}
 
-        // Skip header line
-        pos = skip_line(data, pos);
+/// Streaming iterator over `Update`s decoded one CSV line at a time.
+pub struct CsvLineIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// This CSV format carries no native exchange sequence number, so we
+    /// assign one contiguously from line order. A feed that does carry a
+    /// real exchange sequence would read it from its own column instead —
+    /// `Orderbook`'s gap detection (see `orderbook::Orderbook::apply`) only
+    /// does something useful once that's wired up.
+    next_seq: u64,
+    /// See `CsvReader::config`.
+    config: MarketConfig,
+}
+
+impl<'a> Iterator for CsvLineIter<'a> {
+    type Item = Update;
 
-        while pos < data.len() {
-            let line_start = pos;
+    fn next(&mut self) -> Option<Update> {
+        while self.pos < self.data.len() {
+            let line_start = self.pos;
             // Find the \n (or end of data)
-            let newline_pos = find_newline(data, pos);
+            let newline_pos = find_newline(self.data, self.pos);
             // Content end: strip trailing \r if present
-            let content_end = if newline_pos > line_start && data[newline_pos - 1] == b'\r' {
+            let content_end = if newline_pos > line_start && self.data[newline_pos - 1] == b'\r' {
                 newline_pos - 1
             } else {
                 newline_pos
             };
             // Advance past the \n
-            pos = if newline_pos < data.len() { newline_pos + 1 } else { newline_pos };
+            self.pos = if newline_pos < self.data.len() { newline_pos + 1 } else { newline_pos };
 
             if content_end <= line_start {
                 continue;
             }
-            let line = &data[line_start..content_end];
+            let line = &self.data[line_start..content_end];
 
-            if let Some(update) = parse_line(line) {
-                updates.push(update);
+            if let Some(update) = parse_line(line, self.next_seq, self.config) {
+                self.next_seq += 1;
+                return Some(update);
             }
         }
-
-        updates
+        None
     }
 }
 
+/// Best-effort: ask the kernel to drop clean page cache system-wide via
+/// `/proc/sys/vm/drop_caches`. Requires root; failures are silently ignored
+/// since this is only a benchmark aid for cold-start measurements, not a
+/// correctness requirement — callers should prefer per-mmap eviction via
+/// `CsvReader::evict_from_cache` where possible.
+#[cfg(target_os = "linux")]
+pub fn try_drop_system_caches() {
+    let _ = std::fs::write("/proc/sys/vm/drop_caches", b"1");
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn try_drop_system_caches() {}
+
 /// Skip to the end of the current line.
 #[inline(always)]
 fn skip_line(data: &[u8], mut pos: usize) -> usize {
@@ -75,32 +153,32 @@ fn skip_line(data: &[u8], mut pos: usize) -> usize {
     pos + 1
 }
 
-/// Find position of next \n or end of data.
+/// Find position of next \n or end of data, scanning in bulk via `memchr`
+/// instead of one byte at a time.
 #[inline(always)]
-fn find_newline(data: &[u8], mut pos: usize) -> usize {
-    while pos < data.len() && data[pos] != b'\n' {
-        pos += 1;
-    }
-    pos
+fn find_newline(data: &[u8], pos: usize) -> usize {
+    memchr::memchr(b'\n', &data[pos..]).map_or(data.len(), |i| pos + i)
 }
 
-/// Parse a single CSV line into an Update.
-fn parse_line(line: &[u8]) -> Option<Update> {
+/// Parse a single CSV line into an Update. `exchange_seq` is assigned by the
+/// caller (see `CsvLineIter`) since this CSV format has no native sequence
+/// column.
+fn parse_line(line: &[u8], exchange_seq: u64, config: MarketConfig) -> Option<Update> {
     if line.is_empty() {
         return None;
     }
 
     // Determine type by first character: 's' for snapshot, 'i' for incremental
     match line[0] {
-        b's' => parse_snapshot_line(line),
-        b'i' => parse_incremental_line(line),
+        b's' => parse_snapshot_line(line, exchange_seq, config),
+        b'i' => parse_incremental_line(line, exchange_seq, config),
         _ => None,
     }
 }
 
 /// Parse a snapshot line. Format:
 /// snapshot,binance,BTC/USDT,<timestamp>,,"[[p,s],...]","[[p,s],...]",,
-fn parse_snapshot_line(line: &[u8]) -> Option<Update> {
+fn parse_snapshot_line(line: &[u8], exchange_seq: u64, config: MarketConfig) -> Option<Update> {
     // Fields: type(0), exchange(1), symbol(2), timestamp(3), side(4), bids(5), asks(6), price(7), size(8)
     // For snapshot: side, price, size are empty. bids and asks are JSON arrays potentially quoted.
 
@@ -117,20 +195,24 @@ fn parse_snapshot_line(line: &[u8]) -> Option<Update> {
     let bids_str = fields[5].trim_matches('"');
     let asks_str = fields[6].trim_matches('"');
 
-    let bids = parse_levels_json(bids_str)?;
-    let asks = parse_levels_json(asks_str)?;
+    let bids = parse_levels_json(bids_str, config)?;
+    let asks = parse_levels_json(asks_str, config)?;
 
-    Some(Update::Snapshot { timestamp, bids, asks })
+    Some(Update::Snapshot { timestamp, exchange_seq, bids, asks })
 }
 
 /// Parse a CSV line respecting quoted fields (for JSON arrays with commas).
+/// Jumps between candidate bytes via `memchr2` instead of scanning byte by
+/// byte — quotes and unquoted commas are the only bytes that matter here.
 fn parse_csv_fields(s: &str) -> Vec<&str> {
+    let bytes = s.as_bytes();
     let mut fields = Vec::with_capacity(9);
     let mut start = 0;
     let mut in_quotes = false;
-    let bytes = s.as_bytes();
+    let mut pos = 0;
 
-    for i in 0..bytes.len() {
+    while let Some(rel) = memchr::memchr2(b'"', b',', &bytes[pos..]) {
+        let i = pos + rel;
         match bytes[i] {
             b'"' => in_quotes = !in_quotes,
             b',' if !in_quotes => {
@@ -139,19 +221,21 @@ fn parse_csv_fields(s: &str) -> Vec<&str> {
             }
             _ => {}
         }
+        pos = i + 1;
     }
     fields.push(&s[start..]);
     fields
 }
 
-/// Parse a JSON array of [price, size] pairs into Levels.
+/// Parse a JSON array of [price, size] pairs into Levels, converting prices
+/// to fixed-point via the instrument's `MarketConfig`.
 /// Input: "[[99999.99, 0.527], [99998.86, 3.1404], ...]"
-fn parse_levels_json(s: &str) -> Option<Vec<Level>> {
+fn parse_levels_json(s: &str, config: MarketConfig) -> Option<Vec<Level>> {
     let parsed: Vec<Vec<f64>> = serde_json::from_str(s).ok()?;
     let levels: Vec<Level> = parsed
         .iter()
         .map(|pair| Level {
-            price: Price::from_f64(pair[0]),
+            price: config.to_price(pair[0]),
             qty: Qty(pair[1]),
         })
         .collect();
@@ -160,7 +244,7 @@ fn parse_levels_json(s: &str) -> Option<Vec<Level>> {
 
 /// Parse an incremental line. Format:
 /// incremental,binance,BTC/USDT,<timestamp>,bid/ask,,,<price>,<size>
-fn parse_incremental_line(line: &[u8]) -> Option<Update> {
+fn parse_incremental_line(line: &[u8], exchange_seq: u64, config: MarketConfig) -> Option<Update> {
     // Fast manual parsing — no allocation.
     let mut field_idx = 0;
     let mut field_start = 0;
@@ -210,9 +294,10 @@ fn parse_incremental_line(line: &[u8]) -> Option<Update> {
 
     Some(Update::Incremental {
         timestamp,
+        exchange_seq,
         side,
         level: Level {
-            price: Price::from_f64(price),
+            price: config.to_price(price),
             qty: Qty(size),
         },
     })
@@ -228,15 +313,11 @@ fn parse_u64_fast(bytes: &[u8]) -> u64 {
     result
 }
 
-/// Fast f64 parsing from ASCII bytes.
+/// Fast f64 parsing directly from ASCII bytes — no UTF-8 validation, uses
+/// `fast_float`'s Eisel-Lemire algorithm instead of `str::parse`.
 #[inline(always)]
 fn parse_f64_fast(bytes: &[u8]) -> f64 {
-    // Use fast_float or fallback to std. For our data this is sufficient.
-    if let Ok(s) = std::str::from_utf8(bytes) {
-        s.parse::<f64>().unwrap_or(0.0)
-    } else {
-        0.0
-    }
+    fast_float::parse(bytes).unwrap_or(0.0)
 }
 
 #[cfg(test)]
@@ -246,10 +327,11 @@ mod tests {
     #[test]
     fn test_parse_incremental() {
         let line = b"incremental,binance,BTC/USDT,1700000000100,bid,,,99999.99,0.0";
-        let update = parse_line(line).unwrap();
+        let update = parse_line(line, 1, MarketConfig::default()).unwrap();
         match update {
-            Update::Incremental { timestamp, side, level } => {
+            Update::Incremental { timestamp, exchange_seq, side, level } => {
                 assert_eq!(timestamp, 1700000000100);
+                assert_eq!(exchange_seq, 1);
                 assert_eq!(side, Side::Bid);
                 assert_eq!(level.price, Price::from_f64(99999.99));
                 assert!(level.qty.is_zero());
@@ -0,0 +1,152 @@
+/// Record/replay support for the JSON wire format.
+///
+/// `types`'s `#[cfg(feature = "replay")]` serde impls give `Update` and
+/// `BookNotification` a stable, human-readable schema (prices as decimal
+/// `f64`, sides as `"bid"`/`"ask"`). This module streams that format to and
+/// from newline-delimited JSON, so a recorded exchange feed — or a prior
+/// benchmark run's own output — can be fed back through the engine
+/// byte-for-byte reproducibly, instead of depending on the CSV fixture.
+use crate::types::{BookNotification, Update};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+
+/// Streams `Update`s from a newline-delimited JSON file, one per line.
+/// Blank lines are skipped.
+pub struct ReplayReader {
+    lines: Lines<BufReader<File>>,
+}
+
+impl ReplayReader {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self { lines: BufReader::new(file).lines() })
+    }
+}
+
+impl Iterator for ReplayReader {
+    type Item = io::Result<Update>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(
+                serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            );
+        }
+    }
+}
+
+/// Appends `BookNotification`s to a newline-delimited JSON file, one per
+/// line, so an engine run's output can be recorded and diffed/replayed
+/// later.
+pub struct ReplayWriter {
+    out: BufWriter<File>,
+}
+
+impl ReplayWriter {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self { out: BufWriter::new(File::create(path)?) })
+    }
+
+    pub fn write_notification(&mut self, notif: &BookNotification) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, notif)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.out.write_all(b"\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BookStatus, Level, Price, Qty, Side};
+
+    fn sample_updates() -> Vec<Update> {
+        vec![
+            Update::Snapshot {
+                timestamp: 1,
+                exchange_seq: 1,
+                bids: vec![Level { price: Price::from_f64(100.0), qty: Qty(1.0) }],
+                asks: vec![Level { price: Price::from_f64(101.0), qty: Qty(2.0) }],
+            },
+            Update::Incremental {
+                timestamp: 2,
+                exchange_seq: 2,
+                side: Side::Bid,
+                level: Level { price: Price::from_f64(99.5), qty: Qty(0.5) },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_replay_reader_roundtrips_updates() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("replay_reader_roundtrip_test.ndjson");
+        let updates = sample_updates();
+
+        {
+            let mut out = BufWriter::new(File::create(&path).unwrap());
+            for update in &updates {
+                serde_json::to_writer(&mut out, update).unwrap();
+                out.write_all(b"\n").unwrap();
+            }
+            out.flush().unwrap();
+        }
+
+        let decoded: Vec<Update> = ReplayReader::open(&path)
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(decoded.len(), updates.len());
+        for (original, roundtripped) in updates.iter().zip(decoded.iter()) {
+            assert_eq!(format!("{:?}", original), format!("{:?}", roundtripped));
+        }
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_replay_writer_roundtrips_notification() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("replay_writer_roundtrip_test.ndjson");
+
+        let notif = BookNotification {
+            update_timestamp: 1,
+            engine_send_ns: 2,
+            best_bid: Some(Level { price: Price::from_f64(100.0), qty: Qty(1.0) }),
+            best_ask: Some(Level { price: Price::from_f64(101.0), qty: Qty(2.0) }),
+            seq: 3,
+            status: BookStatus::Live,
+            violation: None,
+        };
+
+        {
+            let mut writer = ReplayWriter::create(&path).unwrap();
+            writer.write_notification(&notif).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let line = std::fs::read_to_string(&path).unwrap();
+        let decoded: BookNotification = serde_json::from_str(line.trim()).unwrap();
+
+        assert_eq!(decoded.update_timestamp, notif.update_timestamp);
+        assert_eq!(decoded.engine_send_ns, notif.engine_send_ns);
+        assert_eq!(decoded.seq, notif.seq);
+        assert_eq!(decoded.status, notif.status);
+        assert_eq!(decoded.best_bid.unwrap().price, notif.best_bid.unwrap().price);
+        assert_eq!(decoded.best_ask.unwrap().price, notif.best_ask.unwrap().price);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
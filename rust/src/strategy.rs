@@ -5,6 +5,7 @@
 /// Also tracks latency from engine → strategy for benchmarking.
 
 use crossbeam_channel::Receiver;
+use crate::histogram::LatencyHistogram;
 use crate::types::*;
 
 /// Run the strategy consumer loop. Blocks until the channel is closed.
@@ -56,8 +57,8 @@ pub struct StrategyStats {
     pub total_latency_ns: u64,
     pub min_latency_ns: u64,
     pub max_latency_ns: u64,
-    /// For percentile calculation — store all latencies when benchmarking.
-    pub latencies: Vec<u64>,
+    /// Bucketed latency distribution — see `histogram::LatencyHistogram`.
+    histogram: LatencyHistogram,
 }
 
 impl StrategyStats {
@@ -67,7 +68,7 @@ impl StrategyStats {
             total_latency_ns: 0,
             min_latency_ns: u64::MAX,
             max_latency_ns: 0,
-            latencies: Vec::with_capacity(8192),
+            histogram: LatencyHistogram::new(),
         }
     }
 
@@ -81,7 +82,7 @@ impl StrategyStats {
         if latency_ns > self.max_latency_ns {
             self.max_latency_ns = latency_ns;
         }
-        self.latencies.push(latency_ns);
+        self.histogram.record(latency_ns);
     }
 
     pub fn avg_latency_ns(&self) -> u64 {
@@ -92,16 +93,20 @@ impl StrategyStats {
     }
 
     pub fn percentile(&self, p: f64) -> u64 {
-        if self.latencies.is_empty() {
-            return 0;
-        }
-        let mut sorted = self.latencies.clone();
-        sorted.sort_unstable();
-        let idx = ((p / 100.0) * (sorted.len() as f64 - 1.0)) as usize;
-        sorted[idx.min(sorted.len() - 1)]
+        self.histogram.percentile(p, self.count)
     }
 
     pub fn median(&self) -> u64 {
         self.percentile(50.0)
     }
+
+    /// Fold another run's stats into this one — e.g. aggregating per-thread
+    /// `StrategyStats` from a multi-consumer benchmark run.
+    pub fn merge(&mut self, other: &StrategyStats) {
+        self.count += other.count;
+        self.total_latency_ns += other.total_latency_ns;
+        self.min_latency_ns = self.min_latency_ns.min(other.min_latency_ns);
+        self.max_latency_ns = self.max_latency_ns.max(other.max_latency_ns);
+        self.histogram.merge(&other.histogram);
+    }
 }
@@ -9,22 +9,49 @@
 pub struct Price(pub u64);
 
 impl Price {
-    /// Convert from f64 price to fixed-point. Rounds to 2 decimal places.
+    /// Convert from f64 price to fixed-point assuming a ×100 (2-decimal)
+    /// scale. Kept for callers without a `MarketConfig` (tests, the BTC/USDT
+    /// default); instruments with a different tick granularity should go
+    /// through `MarketConfig::to_price` instead.
     #[inline(always)]
     pub fn from_f64(p: f64) -> Self {
-        // Multiply by 100 and round to get fixed-point representation
         Price((p * 100.0 + 0.5) as u64)
     }
 
-    /// Convert back to f64 for display purposes only.
+    /// Convert back to f64 assuming a ×100 scale, for display purposes only.
     #[inline(always)]
     pub fn to_f64(self) -> f64 {
         self.0 as f64 / 100.0
     }
+
+    /// Convert back to f64 under an explicit `MarketConfig` price scale.
+    #[inline(always)]
+    pub fn to_f64_scaled(self, price_scale: f64) -> f64 {
+        self.0 as f64 / price_scale
+    }
+}
+
+/// Wire encoding for `Price`: the human `to_f64` decimal form rather than
+/// the raw fixed-point integer, so recorded/replayed JSON is readable and
+/// diffable. Round-trips exactly through `from_f64`'s ×100 scale — callers
+/// on a different `MarketConfig` scale should not rely on this for replay.
+#[cfg(feature = "replay")]
+impl serde::Serialize for Price {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+#[cfg(feature = "replay")]
+impl<'de> serde::Deserialize<'de> for Price {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Price::from_f64)
+    }
 }
 
 /// Quantity stored as raw f64 — no arithmetic needed, just storage & display.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct Qty(pub f64);
 
 impl Qty {
@@ -42,10 +69,36 @@ pub enum Side {
     Ask = 1,
 }
 
+/// Wire encoding for `Side`: lowercase `"bid"`/`"ask"`, matching the
+/// accountsdb connector's `OrderbookSide` convention rather than Rust's
+/// derived `"Bid"`/`"Ask"`.
+#[cfg(feature = "replay")]
+impl serde::Serialize for Side {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Side::Bid => "bid",
+            Side::Ask => "ask",
+        })
+    }
+}
+
+#[cfg(feature = "replay")]
+impl<'de> serde::Deserialize<'de> for Side {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "bid" => Ok(Side::Bid),
+            "ask" => Ok(Side::Ask),
+            other => Err(serde::de::Error::unknown_variant(other, &["bid", "ask"])),
+        }
+    }
+}
+
 /// A single price level update.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct Level {
     pub price: Price,
+    #[cfg_attr(feature = "replay", serde(rename = "size"))]
     pub qty: Qty,
 }
 
@@ -54,14 +107,23 @@ pub type Timestamp = u64;
 
 /// An orderbook update event — either a full snapshot or a single incremental.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "replay", serde(rename_all = "snake_case"))]
 pub enum Update {
     Snapshot {
         timestamp: Timestamp,
+        /// Exchange-provided sequence number. Re-seats `Orderbook`'s gap
+        /// tracking — see `Orderbook::apply`.
+        exchange_seq: u64,
         bids: Vec<Level>,
         asks: Vec<Level>,
     },
     Incremental {
         timestamp: Timestamp,
+        /// Exchange-provided sequence number. Must be exactly one more than
+        /// the previously applied sequence number, or the book is marked
+        /// `Stale` — see `Orderbook::apply`.
+        exchange_seq: u64,
         side: Side,
         level: Level,
     },
@@ -70,6 +132,7 @@ pub enum Update {
 /// Notification sent from the orderbook engine to strategy consumers.
 /// Contains the best bid/ask after each update. Kept small for cache efficiency.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct BookNotification {
     /// Timestamp of the update that triggered this notification (ns).
     pub update_timestamp: Timestamp,
@@ -81,4 +144,144 @@ pub struct BookNotification {
     pub best_ask: Option<Level>,
     /// Sequence number for ordering.
     pub seq: u64,
+    /// Whether the book is known-consistent with the exchange feed, or
+    /// stale due to a detected sequence gap — see `Orderbook::apply`.
+    pub status: BookStatus,
+    /// Set if this update contained a level that violated the instrument's
+    /// `MarketConfig` (tick size, lot size, or minimum size). The offending
+    /// level is dropped rather than applied; this field makes that
+    /// measurable instead of silently distorting the book.
+    pub violation: Option<MarketConfigViolation>,
+}
+
+/// Book staleness status from exchange sequence-gap detection. A book goes
+/// `Stale` when an incremental's `exchange_seq` doesn't immediately follow
+/// the last applied one, and stays `Stale` — dropping further incrementals —
+/// until the next `Update::Snapshot` re-seats it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "replay", serde(rename_all = "snake_case"))]
+pub enum BookStatus {
+    Live,
+    Stale,
+}
+
+/// Depth-N book notification — an opt-in sibling to `BookNotification` for
+/// subscribers that need more than best bid/ask (imbalance, weighted mid,
+/// sweep-cost estimation). Not emitted on the hot path by default; a
+/// consumer asks the engine for one via `Orderbook::depth_notification`.
+#[derive(Debug, Clone)]
+pub struct DepthNotification {
+    /// Timestamp of the update that triggered this notification (ns).
+    pub update_timestamp: Timestamp,
+    /// High-resolution monotonic clock timestamp when notification was sent (ns).
+    pub engine_send_ns: u64,
+    /// Top N bid levels, best first.
+    pub bids: Vec<Level>,
+    /// Top N ask levels, best first.
+    pub asks: Vec<Level>,
+    /// Sequence number for ordering.
+    pub seq: u64,
+}
+
+/// Per-instrument market parameters: price scale, tick size, lot size and
+/// minimum order size. Used by `Orderbook` to normalize/validate incoming
+/// levels instead of silently accepting anything, and replaces the hardcoded
+/// ×100 price scale with something that's exact for instruments with a
+/// different number of decimals (e.g. a low-priced altcoin vs. BTC).
+///
+/// Mirrors the tick/lot/min-size invariants common to exchange order books
+/// (tick size quantizes price, lot size quantizes quantity, min size floors
+/// it).
+#[derive(Debug, Clone, Copy)]
+pub struct MarketConfig {
+    /// Multiplier from human decimal price to the fixed-point `Price` unit.
+    pub price_scale: f64,
+    /// Minimum price increment, in fixed-point `Price` units. A level whose
+    /// price isn't a multiple of this is rejected.
+    pub tick_size: u64,
+    /// Minimum quantity increment, in human units. A level whose quantity
+    /// isn't a multiple of this (within floating-point tolerance) is rejected.
+    pub lot_size: f64,
+    /// Minimum order quantity, in human units. A level below this is rejected.
+    pub min_size: f64,
+}
+
+impl MarketConfig {
+    /// Convert a human decimal price to the fixed-point `Price` representation.
+    #[inline(always)]
+    pub fn to_price(&self, p: f64) -> Price {
+        Price((p * self.price_scale + 0.5) as u64)
+    }
+
+    /// Validate a level against tick size, lot size and minimum size. Returns
+    /// the first violation found, if any.
+    pub fn validate(&self, level: Level) -> Result<(), MarketConfigViolation> {
+        if self.tick_size > 0 && level.price.0 % self.tick_size != 0 {
+            return Err(MarketConfigViolation::InvalidTickSize);
+        }
+        if level.qty.0 < self.min_size {
+            return Err(MarketConfigViolation::BelowMinimumSize);
+        }
+        if self.lot_size > 0.0 {
+            let lots = level.qty.0 / self.lot_size;
+            if (lots - lots.round()).abs() > 1e-9 {
+                return Err(MarketConfigViolation::InvalidLotSize);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for MarketConfig {
+    /// BTC/USDT-like defaults: 2 decimal places, 1-cent ticks, no lot/min-size
+    /// floor — matches the behavior before `MarketConfig` existed.
+    fn default() -> Self {
+        Self { price_scale: 100.0, tick_size: 1, lot_size: 0.0, min_size: 0.0 }
+    }
+}
+
+/// A level rejected by `MarketConfig::validate`. Named after the invariant
+/// it violates, mirroring how exchange order books report malformed orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "replay", serde(rename_all = "snake_case"))]
+pub enum MarketConfigViolation {
+    InvalidTickSize,
+    InvalidLotSize,
+    BelowMinimumSize,
+}
+
+#[cfg(all(test, feature = "replay"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_json_shape_and_roundtrip() {
+        let price = Price::from_f64(12345.67);
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!(json, "12345.67");
+
+        let back: Price = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, price);
+    }
+
+    #[test]
+    fn test_side_json_shape_and_roundtrip() {
+        assert_eq!(serde_json::to_string(&Side::Bid).unwrap(), "\"bid\"");
+        assert_eq!(serde_json::to_string(&Side::Ask).unwrap(), "\"ask\"");
+
+        let back: Side = serde_json::from_str("\"bid\"").unwrap();
+        assert_eq!(back, Side::Bid);
+        let back: Side = serde_json::from_str("\"ask\"").unwrap();
+        assert_eq!(back, Side::Ask);
+    }
+
+    #[test]
+    fn test_level_wire_shape() {
+        let level = Level { price: Price::from_f64(100.0), qty: Qty(1.5) };
+        let json = serde_json::to_value(&level).unwrap();
+        assert_eq!(json["price"], 100.0);
+        assert_eq!(json["size"], 1.5);
+    }
 }